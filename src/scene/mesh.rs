@@ -16,15 +16,61 @@ use crate::{
         math::{aabb::AxisAlignedBoundingBox, frustum::Frustum},
         visitor::{Visit, VisitResult, Visitor},
     },
-    renderer::surface::Surface,
+    renderer::surface::{Surface, SurfaceSharedData, Vertex},
     scene::{base::Base, base::BaseBuilder, graph::Graph},
 };
 use rg3d_core::math::mat4::Mat4;
 use std::{
-    cell::Cell,
+    cell::{Cell, Ref, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
     ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
 };
 
+/// Single blend shape (morph target), also known as vertex animation target. Holds per-vertex
+/// offsets that are added on top of the base geometry, weighted by the mesh's corresponding
+/// morph weight. Used for facial expressions, corrective shapes, etc.
+///
+/// Note: this intentionally ships without a `normal_deltas` field. Morphed normals would need a
+/// `morphed_normal` counterpart to [`Mesh::morphed_position`] and a caller to apply it, neither of
+/// which exists yet; add the field back alongside that when normal morphing is implemented.
+#[derive(Clone, Debug, Default)]
+pub struct MorphTarget {
+    /// Name of the morph target, usually assigned by a 3d modelling tool.
+    pub name: String,
+    /// Per-vertex position offsets, indexed by flat vertex index across all of the mesh's
+    /// surfaces (the vertices of surface 0 first, then those of surface 1, and so on).
+    pub position_deltas: Vec<Vec3>,
+}
+
+impl Visit for MorphTarget {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        visitor.enter_region(name)?;
+
+        self.name.visit("Name", visitor)?;
+        self.position_deltas.visit("PositionDeltas", visitor)?;
+
+        visitor.leave_region()
+    }
+}
+
+/// Per-surface cache of bone (joint) matrices, rebuilt only when the frame it was built for
+/// becomes stale. See [`Mesh::skinning_matrices`].
+#[derive(Debug, Default)]
+struct SkinningCache {
+    frame: Cell<Option<u64>>,
+    matrices: RefCell<Vec<Mat4>>,
+}
+
+impl Clone for SkinningCache {
+    // A cloned mesh (e.g. instantiated from a resource) must not inherit the source mesh's frame
+    // stamp: if it did, querying it with a `frame` that happens to match the stamp it was cloned
+    // with would skip recomputation and hand back the *source* mesh's bone matrices.
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
 /// See module docs.
 #[derive(Clone, Debug)]
 pub struct Mesh {
@@ -32,6 +78,9 @@ pub struct Mesh {
     surfaces: Vec<Surface>,
     bounding_box: Cell<AxisAlignedBoundingBox>,
     bounding_box_dirty: Cell<bool>,
+    morph_targets: Vec<MorphTarget>,
+    morph_weights: Vec<f32>,
+    skinning_cache: Vec<SkinningCache>,
 }
 
 impl Default for Mesh {
@@ -41,6 +90,9 @@ impl Default for Mesh {
             surfaces: Default::default(),
             bounding_box: Default::default(),
             bounding_box_dirty: Cell::new(true),
+            morph_targets: Default::default(),
+            morph_weights: Default::default(),
+            skinning_cache: Default::default(),
         }
     }
 }
@@ -69,10 +121,25 @@ impl Visit for Mesh {
         // recreated on resolve stage! Serialization of surfaces needed for procedural surfaces.
         self.surfaces.visit("Surfaces", visitor)?;
 
+        // Morph targets are procedural data as well, so they have to be saved too.
+        self.morph_targets.visit("MorphTargets", visitor)?;
+        self.morph_weights.visit("MorphWeights", visitor)?;
+
         visitor.leave_region()
     }
 }
 
+/// Deformation mode used by [`Mesh::deform_boundary`].
+#[derive(Clone, Copy, Debug)]
+pub enum BoundaryDeformMode {
+    /// Offsets vertices outward along their own normal.
+    Inflate,
+    /// Translates vertices by a fixed vector.
+    Grab(Vec3),
+    /// Rotates vertices about the boundary axis (the normal of the nearest boundary vertex).
+    Twist,
+}
+
 impl Mesh {
     /// Returns shared reference to array of surfaces.
     #[inline]
@@ -90,6 +157,7 @@ impl Mesh {
     #[inline]
     pub fn clear_surfaces(&mut self) {
         self.surfaces.clear();
+        self.skinning_cache.clear();
         self.bounding_box_dirty.set(true);
     }
 
@@ -97,9 +165,32 @@ impl Mesh {
     #[inline]
     pub fn add_surface(&mut self, surface: Surface) {
         self.surfaces.push(surface);
+        self.skinning_cache.push(SkinningCache::default());
         self.bounding_box_dirty.set(true);
     }
 
+    /// Returns cached bone (joint) matrix palette for the surface at `surface_index`, rebuilding
+    /// it only if `frame` differs from the frame it was last computed for. Recomputing
+    /// `global_transform * inv_bind_pose_transform` for every bone is the dominant cost for
+    /// skinned meshes, so call sites that need the palette more than once per frame (e.g. culling
+    /// followed by rendering) can call this instead of recomputing it themselves. This is an
+    /// opt-in accessor: nothing in this module calls it yet, and existing methods such as
+    /// [`Self::full_world_bounding_box`] still recompute bone matrices inline.
+    pub fn skinning_matrices(&self, surface_index: usize, graph: &Graph, frame: u64) -> Ref<[Mat4]> {
+        let cache = &self.skinning_cache[surface_index];
+        if cache.frame.get() != Some(frame) {
+            let surface = &self.surfaces[surface_index];
+            let mut matrices = cache.matrices.borrow_mut();
+            matrices.clear();
+            matrices.extend(surface.bones().iter().map(|&b| {
+                let bone_node = &graph[b];
+                bone_node.global_transform() * bone_node.inv_bind_pose_transform()
+            }));
+            cache.frame.set(Some(frame));
+        }
+        Ref::map(cache.matrices.borrow(), Vec::as_slice)
+    }
+
     /// Applies given color to all surfaces.
     #[inline]
     pub fn set_color(&mut self, color: Color) {
@@ -108,16 +199,64 @@ impl Mesh {
         }
     }
 
+    /// Returns shared reference to array of morph targets.
+    #[inline]
+    pub fn morph_targets(&self) -> &[MorphTarget] {
+        &self.morph_targets
+    }
+
+    /// Adds a new morph target (blend shape) to the mesh, with zero initial weight.
+    #[inline]
+    pub fn add_morph_target(&mut self, morph_target: MorphTarget) {
+        self.morph_targets.push(morph_target);
+        self.morph_weights.push(0.0);
+        self.bounding_box_dirty.set(true);
+    }
+
+    /// Sets weight of a morph target at given index. Has no effect if `index` is out of bounds.
+    #[inline]
+    pub fn set_morph_weight(&mut self, index: usize, weight: f32) {
+        if let Some(w) = self.morph_weights.get_mut(index) {
+            *w = weight;
+            self.bounding_box_dirty.set(true);
+        }
+    }
+
+    /// Returns mutable reference to array of morph target weights, in the same order as
+    /// [`Self::morph_targets`].
+    #[inline]
+    pub fn morph_weights_mut(&mut self) -> &mut [f32] {
+        self.bounding_box_dirty.set(true);
+        &mut self.morph_weights
+    }
+
+    /// Applies active morph target offsets, weighted by [`Self::morph_weights_mut`], to a single
+    /// vertex position. `vertex_index` must be expressed in the same indexing scheme as the
+    /// morph target's `position_deltas`.
+    fn morphed_position(&self, vertex_index: usize, base_position: Vec3) -> Vec3 {
+        let mut position = base_position;
+        for (target, &weight) in self.morph_targets.iter().zip(self.morph_weights.iter()) {
+            if weight != 0.0 {
+                if let Some(&delta) = target.position_deltas.get(vertex_index) {
+                    position += delta.scale(weight);
+                }
+            }
+        }
+        position
+    }
+
     /// Performs lazy bounding box evaluation. Bounding box presented in *local coordinates*
     /// WARNING: This method does *not* includes bounds of bones!
     pub fn bounding_box(&self) -> AxisAlignedBoundingBox {
         if self.bounding_box_dirty.get() {
             let mut bounding_box = AxisAlignedBoundingBox::default();
+            let mut vertex_index = 0;
             for surface in self.surfaces.iter() {
                 let data = surface.data();
                 let data = data.lock().unwrap();
                 for vertex in data.get_vertices() {
-                    bounding_box.add_point(vertex.position);
+                    bounding_box.add_point(self.morphed_position(vertex_index, vertex.position));
+                    vertex_index += 1;
                 }
             }
             self.bounding_box.set(bounding_box);
@@ -130,33 +269,38 @@ impl Mesh {
     /// intended to use every frame! WARNING: This method does *not* includes bounds of bones!
     pub fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
         let mut bounding_box = AxisAlignedBoundingBox::default();
+        let mut vertex_index = 0;
         for surface in self.surfaces.iter() {
             let data = surface.data();
             let data = data.lock().unwrap();
             for vertex in data.get_vertices() {
-                bounding_box.add_point(self.global_transform().transform_vector(vertex.position));
+                let position = self.morphed_position(vertex_index, vertex.position);
+                bounding_box.add_point(self.global_transform().transform_vector(position));
+                vertex_index += 1;
             }
         }
         bounding_box
     }
 
     /// Calculate bounding box in *world coordinates* including influence of bones. This method
-    /// is very heavy and not intended to use every frame!
+    /// is very heavy and not intended to use every frame! Bone matrices are recomputed on every
+    /// call; use [`Self::skinning_matrices`] directly if you already have a cached palette for
+    /// the current frame (e.g. from culling or rendering) and want to reuse it.
     pub fn full_world_bounding_box(&self, graph: &Graph) -> AxisAlignedBoundingBox {
         let mut bounding_box = AxisAlignedBoundingBox::default();
+        let mut vertex_index = 0;
         for surface in self.surfaces.iter() {
             let data = surface.data();
             let data = data.lock().unwrap();
             if surface.bones().is_empty() {
                 for vertex in data.get_vertices() {
-                    bounding_box
-                        .add_point(self.global_transform().transform_vector(vertex.position));
+                    let position = self.morphed_position(vertex_index, vertex.position);
+                    bounding_box.add_point(self.global_transform().transform_vector(position));
+                    vertex_index += 1;
                 }
             } else {
                 // Special case for skinned surface. Its actual bounds defined only by bones
                 // influence.
-
-                // Precalculate bone matrices first to speed up calculations.
                 let bone_matrices = surface
                     .bones()
                     .iter()
@@ -167,12 +311,15 @@ impl Mesh {
                     .collect::<Vec<Mat4>>();
 
                 for vertex in data.get_vertices() {
+                    let morphed = self.morphed_position(vertex_index, vertex.position);
+                    vertex_index += 1;
+
                     let mut position = Vec3::ZERO;
                     for (&bone_index, &weight) in
                         vertex.bone_indices.iter().zip(vertex.bone_weights.iter())
                     {
                         position += bone_matrices[bone_index as usize]
-                            .transform_vector(vertex.position)
+                            .transform_vector(morphed)
                             .scale(weight);
                     }
 
@@ -201,12 +348,264 @@ impl Mesh {
 
         false
     }
+
+    /// Deforms an open mesh boundary at runtime, similar to a sculpting boundary brush.
+    /// `active_vertex` is a flat index into the mesh's combined vertex buffer (the same indexing
+    /// used by [`Self::add_morph_target`]). The boundary vertex nearest to it is used as the
+    /// origin of the deformation, which is then propagated using the topological (BFS)
+    /// distance from that boundary vertex, falling off smoothly over `radius` edge hops via a
+    /// `smoothstep`. Meshes with no boundary edges (watertight surfaces) are left untouched;
+    /// non-manifold edges (referenced by more than two triangles) are treated as interior.
+    pub fn deform_boundary(
+        &mut self,
+        active_vertex: usize,
+        radius: f32,
+        mode: BoundaryDeformMode,
+        strength: f32,
+    ) {
+        let mut local_vertex = active_vertex;
+        let mut target_surface = None;
+        for (surface_index, surface) in self.surfaces.iter().enumerate() {
+            let vertex_count = surface.data().lock().unwrap().get_vertices().len();
+            if local_vertex < vertex_count {
+                target_surface = Some(surface_index);
+                break;
+            }
+            local_vertex -= vertex_count;
+        }
+
+        let surface_index = match target_surface {
+            Some(surface_index) => surface_index,
+            None => return,
+        };
+
+        let data = self.surfaces[surface_index].data().clone();
+        let mut data = data.lock().unwrap();
+
+        let triangles = data.get_triangles().to_vec();
+
+        // Count how many triangles reference each edge: exactly one means it is a boundary edge.
+        // An edge referenced by more than two triangles is non-manifold and treated as interior.
+        let mut edge_triangle_count: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        for triangle in &triangles {
+            for i in 0..3 {
+                let a = triangle[i];
+                let b = triangle[(i + 1) % 3];
+                let key = if a < b { (a, b) } else { (b, a) };
+                *edge_triangle_count.entry(key).or_insert(0) += 1;
+                adjacency.entry(a).or_default().push(b);
+                adjacency.entry(b).or_default().push(a);
+            }
+        }
+
+        let boundary_vertices: HashSet<u32> = edge_triangle_count
+            .into_iter()
+            .filter(|&(_, count)| count == 1)
+            .flat_map(|(edge, _)| [edge.0, edge.1])
+            .collect();
+
+        if boundary_vertices.is_empty() {
+            // Watertight surface, there is no boundary to deform.
+            return;
+        }
+
+        let vertices = data.get_vertices();
+        let active_position = vertices[local_vertex].position;
+        let nearest_boundary = boundary_vertices
+            .iter()
+            .copied()
+            .min_by(|&a, &b| {
+                sq_distance(vertices[a as usize].position, active_position)
+                    .partial_cmp(&sq_distance(vertices[b as usize].position, active_position))
+                    .unwrap()
+            })
+            .unwrap();
+        let pivot_position = vertices[nearest_boundary as usize].position;
+        // `rotate_around_axis` requires a unit axis, but stored vertex normals aren't guaranteed
+        // to be unit length (e.g. on imported meshes), so normalize before using it as one.
+        let pivot_normal = normalized(vertices[nearest_boundary as usize].normal);
+
+        // Breadth-first search over vertex adjacency, measuring topological distance (in edge
+        // hops) from the boundary vertex closest to `active_vertex`.
+        let max_hops = radius.ceil().max(0.0) as u32;
+        let mut distance = HashMap::new();
+        let mut queue = VecDeque::new();
+        distance.insert(nearest_boundary, 0u32);
+        queue.push_back(nearest_boundary);
+        while let Some(vertex) = queue.pop_front() {
+            let hops = distance[&vertex];
+            if hops >= max_hops {
+                continue;
+            }
+            if let Some(neighbors) = adjacency.get(&vertex) {
+                for &neighbor in neighbors {
+                    if !distance.contains_key(&neighbor) {
+                        distance.insert(neighbor, hops + 1);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut deformed = HashSet::new();
+        let safe_radius = radius.max(f32::EPSILON);
+        let vertices_mut = data.get_vertices_mut();
+        for (&vertex_index, &hops) in &distance {
+            let t = (1.0 - hops as f32 / safe_radius).clamp(0.0, 1.0);
+            let weight = t * t * (3.0 - 2.0 * t);
+            if weight <= 0.0 {
+                continue;
+            }
+
+            let vertex = &mut vertices_mut[vertex_index as usize];
+            vertex.position = match mode {
+                BoundaryDeformMode::Inflate => {
+                    // Normalize first: stored vertex normals aren't guaranteed to be unit
+                    // length, and displacement magnitude should track strength*weight only.
+                    vertex.position + normalized(vertex.normal).scale(strength * weight)
+                }
+                BoundaryDeformMode::Grab(direction) => {
+                    vertex.position + direction.scale(strength * weight)
+                }
+                BoundaryDeformMode::Twist => {
+                    let offset = vertex.position - pivot_position;
+                    pivot_position + rotate_around_axis(offset, pivot_normal, strength * weight)
+                }
+            };
+            deformed.insert(vertex_index);
+        }
+
+        // Recompute normals of the deformed vertices from their surrounding triangle faces.
+        let mut normal_accum: HashMap<u32, Vec3> = HashMap::new();
+        for triangle in &triangles {
+            if triangle.iter().any(|index| deformed.contains(index)) {
+                let p0 = vertices_mut[triangle[0] as usize].position;
+                let p1 = vertices_mut[triangle[1] as usize].position;
+                let p2 = vertices_mut[triangle[2] as usize].position;
+                let e1 = p1 - p0;
+                let e2 = p2 - p0;
+                let face_normal = Vec3::new(
+                    e1.y * e2.z - e1.z * e2.y,
+                    e1.z * e2.x - e1.x * e2.z,
+                    e1.x * e2.y - e1.y * e2.x,
+                );
+                for &index in triangle {
+                    if deformed.contains(&index) {
+                        let entry = normal_accum.entry(index).or_insert(Vec3::ZERO);
+                        *entry += face_normal;
+                    }
+                }
+            }
+        }
+        for (vertex_index, normal) in normal_accum {
+            let len = (normal.x * normal.x + normal.y * normal.y + normal.z * normal.z).sqrt();
+            if len > f32::EPSILON {
+                vertices_mut[vertex_index as usize].normal =
+                    Vec3::new(normal.x / len, normal.y / len, normal.z / len);
+            }
+        }
+
+        drop(data);
+        self.bounding_box_dirty.set(true);
+    }
+
+    /// Packs `global_transform` into a renderer-friendly layout: a row-major 4x3 affine matrix
+    /// (the translation plus 3x3 linear part, dropping the trailing `[0, 0, 0, 1]` row) and the
+    /// 3x3 inverse-transpose of its linear part for transforming normals. Together these are
+    /// ~30% smaller than uploading two full [`Mat4`]s per instance, with both matrices
+    /// reconstructible in the shader. Does not replace the existing [`Base::global_transform`]
+    /// accessor, which remains available for CPU-side use.
+    pub fn render_transform_data(&self) -> ([f32; 12], [f32; 9]) {
+        pack_transform(&self.global_transform())
+    }
+}
+
+/// Does the packing described on [`Mesh::render_transform_data`]; pulled out as a free function
+/// of a plain `Mat4` so the linear algebra can be unit tested without a scene graph.
+fn pack_transform(m: &Mat4) -> ([f32; 12], [f32; 9]) {
+    let mut affine = [0.0f32; 12];
+    affine.copy_from_slice(&m.f[0..12]);
+
+    // Upper-left 3x3 (rotation/scale) part of the transform, row-major.
+    let r = [
+        [m.f[0], m.f[1], m.f[2]],
+        [m.f[4], m.f[5], m.f[6]],
+        [m.f[8], m.f[9], m.f[10]],
+    ];
+
+    // Cofactors of `r`; for a 3x3 matrix the inverse-transpose equals the cofactor matrix
+    // divided by the determinant, so this skips computing and then re-transposing a
+    // separate inverse.
+    let cofactors = [
+        [
+            r[1][1] * r[2][2] - r[1][2] * r[2][1],
+            r[1][2] * r[2][0] - r[1][0] * r[2][2],
+            r[1][0] * r[2][1] - r[1][1] * r[2][0],
+        ],
+        [
+            r[0][2] * r[2][1] - r[0][1] * r[2][2],
+            r[0][0] * r[2][2] - r[0][2] * r[2][0],
+            r[0][1] * r[2][0] - r[0][0] * r[2][1],
+        ],
+        [
+            r[0][1] * r[1][2] - r[0][2] * r[1][1],
+            r[0][2] * r[1][0] - r[0][0] * r[1][2],
+            r[0][0] * r[1][1] - r[0][1] * r[1][0],
+        ],
+    ];
+    let det = r[0][0] * cofactors[0][0] + r[0][1] * cofactors[0][1] + r[0][2] * cofactors[0][2];
+    let inv_det = if det.abs() > f32::EPSILON { 1.0 / det } else { 0.0 };
+
+    let normal = [
+        cofactors[0][0] * inv_det,
+        cofactors[0][1] * inv_det,
+        cofactors[0][2] * inv_det,
+        cofactors[1][0] * inv_det,
+        cofactors[1][1] * inv_det,
+        cofactors[1][2] * inv_det,
+        cofactors[2][0] * inv_det,
+        cofactors[2][1] * inv_det,
+        cofactors[2][2] * inv_det,
+    ];
+
+    (affine, normal)
+}
+
+/// Rotates `v` about unit `axis` by `angle` radians, using Rodrigues' rotation formula.
+fn rotate_around_axis(v: Vec3, axis: Vec3, angle: f32) -> Vec3 {
+    let (sin, cos) = angle.sin_cos();
+    let dot = v.x * axis.x + v.y * axis.y + v.z * axis.z;
+    let cross = Vec3::new(
+        axis.y * v.z - axis.z * v.y,
+        axis.z * v.x - axis.x * v.z,
+        axis.x * v.y - axis.y * v.x,
+    );
+    v.scale(cos) + cross.scale(sin) + axis.scale(dot * (1.0 - cos))
+}
+
+/// Squared distance between two points, avoiding the `sqrt` needed for nearest-vertex search.
+fn sq_distance(a: Vec3, b: Vec3) -> f32 {
+    let d = a - b;
+    d.x * d.x + d.y * d.y + d.z * d.z
+}
+
+/// Normalizes `v`, returning it unchanged if it is too short to normalize safely.
+fn normalized(v: Vec3) -> Vec3 {
+    let len = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if len > f32::EPSILON {
+        Vec3::new(v.x / len, v.y / len, v.z / len)
+    } else {
+        v
+    }
 }
 
 /// Mesh builder allows you to construct mesh in declarative manner.
 pub struct MeshBuilder {
     base_builder: BaseBuilder,
     surfaces: Vec<Surface>,
+    morph_targets: Vec<MorphTarget>,
+    color: Option<Color>,
 }
 
 impl MeshBuilder {
@@ -215,6 +614,8 @@ impl MeshBuilder {
         Self {
             base_builder,
             surfaces: Default::default(),
+            morph_targets: Default::default(),
+            color: None,
         }
     }
 
@@ -224,14 +625,58 @@ impl MeshBuilder {
         self
     }
 
+    /// Appends a single surface to the mesh being built.
+    #[must_use]
+    pub fn with_surface(mut self, surface: Surface) -> Self {
+        self.surfaces.push(surface);
+        self
+    }
+
+    /// Applies given color to all surfaces once the mesh is built.
+    #[must_use]
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets desired morph targets for mesh, each starting with zero weight.
+    #[must_use]
+    pub fn with_morph_targets(mut self, morph_targets: Vec<MorphTarget>) -> Self {
+        self.morph_targets = morph_targets;
+        self
+    }
+
+    /// Adds a procedural surface generated by triangulating the given scalar field (e.g. a
+    /// signed distance function) with marching cubes. See [`Surface::from_implicit`] for details.
+    pub fn with_implicit_surface(
+        mut self,
+        field: impl Fn(Vec3) -> f32,
+        bounds: AxisAlignedBoundingBox,
+        resolution: (usize, usize, usize),
+        isovalue: f32,
+    ) -> Self {
+        self.surfaces
+            .push(Surface::from_implicit(field, bounds, resolution, isovalue));
+        self
+    }
+
     /// Creates new mesh.
     pub fn build(self) -> Mesh {
-        Mesh {
+        let morph_weights = vec![0.0; self.morph_targets.len()];
+        let skinning_cache = vec![SkinningCache::default(); self.surfaces.len()];
+        let mut mesh = Mesh {
             base: self.base_builder.build(),
             surfaces: self.surfaces,
             bounding_box: Default::default(),
             bounding_box_dirty: Cell::new(true),
+            morph_targets: self.morph_targets,
+            morph_weights,
+            skinning_cache,
+        };
+        if let Some(color) = self.color {
+            mesh.set_color(color);
         }
+        mesh
     }
 
     /// Creates new node instance.
@@ -239,3 +684,639 @@ impl MeshBuilder {
         Node::Mesh(self.build())
     }
 }
+
+impl Surface {
+    /// Triangulates a scalar field (e.g. a signed distance function) into renderable geometry
+    /// using the standard marching cubes algorithm, letting users build metaballs, CSG shapes and
+    /// voxel terrain directly into a [`Mesh`] at runtime. `resolution` is the per-axis cell
+    /// count (the field is sampled at `resolution + 1` points per axis), and `isovalue` selects which level set of `field` becomes the surface
+    /// (`0.0` for a typical signed distance field).
+    pub fn from_implicit(
+        field: impl Fn(Vec3) -> f32,
+        bounds: AxisAlignedBoundingBox,
+        resolution: (usize, usize, usize),
+        isovalue: f32,
+    ) -> Self {
+        let (vertices, indices) = marching_cubes(&field, bounds, resolution, isovalue);
+        // `marching_cubes` hands back a flat index buffer; `SurfaceSharedData` (see its use via
+        // `get_triangles` in `Mesh::deform_boundary`) expects triangles grouped by index instead.
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect::<Vec<_>>();
+        Surface::new(Arc::new(Mutex::new(SurfaceSharedData::new(
+            vertices, triangles, false,
+        ))))
+    }
+}
+
+/// Corner offsets of a marching cubes cell, in the same winding used by [`EDGE_CONNECTION`] and
+/// the classic edge/triangle tables below.
+const CORNER_OFFSETS: [(f32, f32, f32); 8] = [
+    (0.0, 0.0, 0.0),
+    (1.0, 0.0, 0.0),
+    (1.0, 1.0, 0.0),
+    (0.0, 1.0, 0.0),
+    (0.0, 0.0, 1.0),
+    (1.0, 0.0, 1.0),
+    (1.0, 1.0, 1.0),
+    (0.0, 1.0, 1.0),
+];
+
+/// Maps each of the 12 cell edges to the pair of corner indices (into [`CORNER_OFFSETS`]) it
+/// connects.
+const EDGE_CONNECTION: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// For each of the 256 corner-sign configurations, a bit mask of which of the 12 edges are
+/// crossed by the isosurface. Standard marching cubes edge table.
+#[rustfmt::skip]
+const EDGE_TABLE: [u16; 256] = [
+    0x0, 0x109, 0x203, 0x30a, 0x406, 0x50f, 0x605, 0x70c,
+    0x80c, 0x905, 0xa0f, 0xb06, 0xc0a, 0xd03, 0xe09, 0xf00,
+    0x190, 0x99, 0x393, 0x29a, 0x596, 0x49f, 0x795, 0x69c,
+    0x99c, 0x895, 0xb9f, 0xa96, 0xd9a, 0xc93, 0xf99, 0xe90,
+    0x230, 0x339, 0x33, 0x13a, 0x636, 0x73f, 0x435, 0x53c,
+    0xa3c, 0xb35, 0x83f, 0x936, 0xe3a, 0xf33, 0xc39, 0xd30,
+    0x3a0, 0x2a9, 0x1a3, 0xaa, 0x7a6, 0x6af, 0x5a5, 0x4ac,
+    0xbac, 0xaa5, 0x9af, 0x8a6, 0xfaa, 0xea3, 0xda9, 0xca0,
+    0x460, 0x569, 0x663, 0x76a, 0x66, 0x16f, 0x265, 0x36c,
+    0xc6c, 0xd65, 0xe6f, 0xf66, 0x86a, 0x963, 0xa69, 0xb60,
+    0x5f0, 0x4f9, 0x7f3, 0x6fa, 0x1f6, 0xff, 0x3f5, 0x2fc,
+    0xdfc, 0xcf5, 0xfff, 0xef6, 0x9fa, 0x8f3, 0xbf9, 0xaf0,
+    0x650, 0x759, 0x453, 0x55a, 0x256, 0x35f, 0x55, 0x15c,
+    0xe5c, 0xf55, 0xc5f, 0xd56, 0xa5a, 0xb53, 0x859, 0x950,
+    0x7c0, 0x6c9, 0x5c3, 0x4ca, 0x3c6, 0x2cf, 0x1c5, 0xcc,
+    0xfcc, 0xec5, 0xdcf, 0xcc6, 0xbca, 0xac3, 0x9c9, 0x8c0,
+    0x8c0, 0x9c9, 0xac3, 0xbca, 0xcc6, 0xdcf, 0xec5, 0xfcc,
+    0xcc, 0x1c5, 0x2cf, 0x3c6, 0x4ca, 0x5c3, 0x6c9, 0x7c0,
+    0x950, 0x859, 0xb53, 0xa5a, 0xd56, 0xc5f, 0xf55, 0xe5c,
+    0x15c, 0x55, 0x35f, 0x256, 0x55a, 0x453, 0x759, 0x650,
+    0xaf0, 0xbf9, 0x8f3, 0x9fa, 0xef6, 0xfff, 0xcf5, 0xdfc,
+    0x2fc, 0x3f5, 0xff, 0x1f6, 0x6fa, 0x7f3, 0x4f9, 0x5f0,
+    0xb60, 0xa69, 0x963, 0x86a, 0xf66, 0xe6f, 0xd65, 0xc6c,
+    0x36c, 0x265, 0x16f, 0x66, 0x76a, 0x663, 0x569, 0x460,
+    0xca0, 0xda9, 0xea3, 0xfaa, 0x8a6, 0x9af, 0xaa5, 0xbac,
+    0x4ac, 0x5a5, 0x6af, 0x7a6, 0xaa, 0x1a3, 0x2a9, 0x3a0,
+    0xd30, 0xc39, 0xf33, 0xe3a, 0x936, 0x83f, 0xb35, 0xa3c,
+    0x53c, 0x435, 0x73f, 0x636, 0x13a, 0x33, 0x339, 0x230,
+    0xe90, 0xf99, 0xc93, 0xd9a, 0xa96, 0xb9f, 0x895, 0x99c,
+    0x69c, 0x795, 0x49f, 0x596, 0x29a, 0x393, 0x99, 0x190,
+    0xf00, 0xe09, 0xd03, 0xc0a, 0xb06, 0xa0f, 0x905, 0x80c,
+    0x70c, 0x605, 0x50f, 0x406, 0x30a, 0x203, 0x109, 0x0,
+];
+
+/// For each of the 256 corner-sign configurations, up to 5 triangles (as edge index triples,
+/// terminated by `-1`) connecting the crossed edges. Standard marching cubes triangle table.
+#[rustfmt::skip]
+const TRI_TABLE: [[i8; 16]; 256] = [
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 1, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 8, 3, 9, 8, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, 1, 2, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 2, 10, 0, 2, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 3, 2, 10, 8, 10, 9, 8, -1, -1, -1, -1, -1, -1, -1],
+    [3, 11, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 11, 2, 8, 11, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 9, 0, 2, 3, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 11, 2, 1, 9, 11, 9, 8, 11, -1, -1, -1, -1, -1, -1, -1],
+    [3, 10, 1, 11, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 10, 1, 0, 8, 10, 8, 11, 10, -1, -1, -1, -1, -1, -1, -1],
+    [3, 9, 0, 3, 11, 9, 11, 10, 9, -1, -1, -1, -1, -1, -1, -1],
+    [9, 8, 10, 10, 8, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 7, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 3, 0, 7, 3, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 1, 9, 8, 4, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 1, 9, 4, 7, 1, 7, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, 8, 4, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 4, 7, 3, 0, 4, 1, 2, 10, -1, -1, -1, -1, -1, -1, -1],
+    [9, 2, 10, 9, 0, 2, 8, 4, 7, -1, -1, -1, -1, -1, -1, -1],
+    [2, 10, 9, 2, 9, 7, 2, 7, 3, 7, 9, 4, -1, -1, -1, -1],
+    [8, 4, 7, 3, 11, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11, 4, 7, 11, 2, 4, 2, 0, 4, -1, -1, -1, -1, -1, -1, -1],
+    [9, 0, 1, 8, 4, 7, 2, 3, 11, -1, -1, -1, -1, -1, -1, -1],
+    [4, 7, 11, 9, 4, 11, 9, 11, 2, 9, 2, 1, -1, -1, -1, -1],
+    [3, 10, 1, 3, 11, 10, 7, 8, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 11, 10, 1, 4, 11, 1, 0, 4, 7, 11, 4, -1, -1, -1, -1],
+    [4, 7, 8, 9, 0, 11, 9, 11, 10, 11, 0, 3, -1, -1, -1, -1],
+    [4, 7, 11, 4, 11, 9, 9, 11, 10, -1, -1, -1, -1, -1, -1, -1],
+    [9, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 5, 4, 0, 8, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 5, 4, 1, 5, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [8, 5, 4, 8, 3, 5, 3, 1, 5, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, 9, 5, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 0, 8, 1, 2, 10, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [5, 2, 10, 5, 4, 2, 4, 0, 2, -1, -1, -1, -1, -1, -1, -1],
+    [2, 10, 5, 3, 2, 5, 3, 5, 4, 3, 4, 8, -1, -1, -1, -1],
+    [9, 5, 4, 2, 3, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 11, 2, 0, 8, 11, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [0, 5, 4, 0, 1, 5, 2, 3, 11, -1, -1, -1, -1, -1, -1, -1],
+    [2, 1, 5, 2, 5, 8, 2, 8, 11, 4, 8, 5, -1, -1, -1, -1],
+    [10, 3, 11, 10, 1, 3, 9, 5, 4, -1, -1, -1, -1, -1, -1, -1],
+    [4, 9, 5, 0, 8, 1, 8, 10, 1, 8, 11, 10, -1, -1, -1, -1],
+    [5, 4, 0, 5, 0, 11, 5, 11, 10, 11, 0, 3, -1, -1, -1, -1],
+    [5, 4, 8, 5, 8, 10, 10, 8, 11, -1, -1, -1, -1, -1, -1, -1],
+    [9, 7, 8, 5, 7, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 3, 0, 9, 5, 3, 5, 7, 3, -1, -1, -1, -1, -1, -1, -1],
+    [0, 7, 8, 0, 1, 7, 1, 5, 7, -1, -1, -1, -1, -1, -1, -1],
+    [1, 5, 3, 3, 5, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 7, 8, 9, 5, 7, 10, 1, 2, -1, -1, -1, -1, -1, -1, -1],
+    [10, 1, 2, 9, 5, 0, 5, 3, 0, 5, 7, 3, -1, -1, -1, -1],
+    [8, 0, 2, 8, 2, 5, 8, 5, 7, 10, 5, 2, -1, -1, -1, -1],
+    [2, 10, 5, 2, 5, 3, 3, 5, 7, -1, -1, -1, -1, -1, -1, -1],
+    [7, 9, 5, 7, 8, 9, 3, 11, 2, -1, -1, -1, -1, -1, -1, -1],
+    [9, 5, 7, 9, 7, 2, 9, 2, 0, 2, 7, 11, -1, -1, -1, -1],
+    [2, 3, 11, 0, 1, 8, 1, 7, 8, 1, 5, 7, -1, -1, -1, -1],
+    [11, 2, 1, 11, 1, 7, 7, 1, 5, -1, -1, -1, -1, -1, -1, -1],
+    [9, 5, 8, 8, 5, 7, 10, 1, 3, 10, 3, 11, -1, -1, -1, -1],
+    [5, 7, 0, 5, 0, 9, 7, 11, 0, 1, 0, 10, 11, 10, 0, -1],
+    [11, 10, 0, 11, 0, 3, 10, 5, 0, 8, 0, 7, 5, 7, 0, -1],
+    [11, 10, 5, 7, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10, 6, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, 5, 10, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 0, 1, 5, 10, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 8, 3, 1, 9, 8, 5, 10, 6, -1, -1, -1, -1, -1, -1, -1],
+    [1, 6, 5, 2, 6, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 6, 5, 1, 2, 6, 3, 0, 8, -1, -1, -1, -1, -1, -1, -1],
+    [9, 6, 5, 9, 0, 6, 0, 2, 6, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 8, 5, 8, 2, 5, 2, 6, 3, 2, 8, -1, -1, -1, -1],
+    [2, 3, 11, 10, 6, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11, 0, 8, 11, 2, 0, 10, 6, 5, -1, -1, -1, -1, -1, -1, -1],
+    [0, 1, 9, 2, 3, 11, 5, 10, 6, -1, -1, -1, -1, -1, -1, -1],
+    [5, 10, 6, 1, 9, 2, 9, 11, 2, 9, 8, 11, -1, -1, -1, -1],
+    [6, 3, 11, 6, 5, 3, 5, 1, 3, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 11, 0, 11, 5, 0, 5, 1, 5, 11, 6, -1, -1, -1, -1],
+    [3, 11, 6, 0, 3, 6, 0, 6, 5, 0, 5, 9, -1, -1, -1, -1],
+    [6, 5, 9, 6, 9, 11, 11, 9, 8, -1, -1, -1, -1, -1, -1, -1],
+    [5, 10, 6, 4, 7, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 3, 0, 4, 7, 3, 6, 5, 10, -1, -1, -1, -1, -1, -1, -1],
+    [1, 9, 0, 5, 10, 6, 8, 4, 7, -1, -1, -1, -1, -1, -1, -1],
+    [10, 6, 5, 1, 9, 7, 1, 7, 3, 7, 9, 4, -1, -1, -1, -1],
+    [6, 1, 2, 6, 5, 1, 4, 7, 8, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 5, 5, 2, 6, 3, 0, 4, 3, 4, 7, -1, -1, -1, -1],
+    [8, 4, 7, 9, 0, 5, 0, 6, 5, 0, 2, 6, -1, -1, -1, -1],
+    [7, 3, 9, 7, 9, 4, 3, 2, 9, 5, 9, 6, 2, 6, 9, -1],
+    [3, 11, 2, 7, 8, 4, 10, 6, 5, -1, -1, -1, -1, -1, -1, -1],
+    [5, 10, 6, 4, 7, 2, 4, 2, 0, 2, 7, 11, -1, -1, -1, -1],
+    [0, 1, 9, 4, 7, 8, 2, 3, 11, 5, 10, 6, -1, -1, -1, -1],
+    [9, 2, 1, 9, 11, 2, 9, 4, 11, 7, 11, 4, 5, 10, 6, -1],
+    [8, 4, 7, 3, 11, 5, 3, 5, 1, 5, 11, 6, -1, -1, -1, -1],
+    [5, 1, 11, 5, 11, 6, 1, 0, 11, 7, 11, 4, 0, 4, 11, -1],
+    [0, 5, 9, 0, 6, 5, 0, 3, 6, 11, 6, 3, 8, 4, 7, -1],
+    [6, 5, 9, 6, 9, 11, 4, 7, 9, 7, 11, 9, -1, -1, -1, -1],
+    [10, 4, 9, 6, 4, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 6, 4, 9, 10, 0, 8, 3, -1, -1, -1, -1, -1, -1, -1],
+    [10, 0, 1, 10, 6, 0, 6, 4, 0, -1, -1, -1, -1, -1, -1, -1],
+    [8, 3, 1, 8, 1, 6, 8, 6, 4, 6, 1, 10, -1, -1, -1, -1],
+    [1, 4, 9, 1, 2, 4, 2, 6, 4, -1, -1, -1, -1, -1, -1, -1],
+    [3, 0, 8, 1, 2, 9, 2, 4, 9, 2, 6, 4, -1, -1, -1, -1],
+    [0, 2, 4, 4, 2, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [8, 3, 2, 8, 2, 4, 4, 2, 6, -1, -1, -1, -1, -1, -1, -1],
+    [10, 4, 9, 10, 6, 4, 11, 2, 3, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 2, 2, 8, 11, 4, 9, 10, 4, 10, 6, -1, -1, -1, -1],
+    [3, 11, 2, 0, 1, 6, 0, 6, 4, 6, 1, 10, -1, -1, -1, -1],
+    [6, 4, 1, 6, 1, 10, 4, 8, 1, 2, 1, 11, 8, 11, 1, -1],
+    [9, 6, 4, 9, 3, 6, 9, 1, 3, 11, 6, 3, -1, -1, -1, -1],
+    [8, 11, 1, 8, 1, 0, 11, 6, 1, 9, 1, 4, 6, 4, 1, -1],
+    [3, 11, 6, 3, 6, 0, 0, 6, 4, -1, -1, -1, -1, -1, -1, -1],
+    [6, 4, 8, 11, 6, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 10, 6, 7, 8, 10, 8, 9, 10, -1, -1, -1, -1, -1, -1, -1],
+    [0, 7, 3, 0, 10, 7, 0, 9, 10, 6, 7, 10, -1, -1, -1, -1],
+    [10, 6, 7, 1, 10, 7, 1, 7, 8, 1, 8, 0, -1, -1, -1, -1],
+    [10, 6, 7, 10, 7, 1, 1, 7, 3, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 6, 1, 6, 8, 1, 8, 9, 8, 6, 7, -1, -1, -1, -1],
+    [2, 6, 9, 2, 9, 1, 6, 7, 9, 0, 9, 3, 7, 3, 9, -1],
+    [7, 8, 0, 7, 0, 6, 6, 0, 2, -1, -1, -1, -1, -1, -1, -1],
+    [7, 3, 2, 6, 7, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 3, 11, 10, 6, 8, 10, 8, 9, 8, 6, 7, -1, -1, -1, -1],
+    [2, 0, 7, 2, 7, 11, 0, 9, 7, 6, 7, 10, 9, 10, 7, -1],
+    [1, 8, 0, 1, 7, 8, 1, 10, 7, 6, 7, 10, 2, 3, 11, -1],
+    [11, 2, 1, 11, 1, 7, 10, 6, 1, 6, 7, 1, -1, -1, -1, -1],
+    [8, 9, 6, 8, 6, 7, 9, 1, 6, 11, 6, 3, 1, 3, 6, -1],
+    [0, 9, 1, 11, 6, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 8, 0, 7, 0, 6, 3, 11, 0, 11, 6, 0, -1, -1, -1, -1],
+    [7, 11, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 6, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 0, 8, 11, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 1, 9, 11, 7, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [8, 1, 9, 8, 3, 1, 11, 7, 6, -1, -1, -1, -1, -1, -1, -1],
+    [10, 1, 2, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, 3, 0, 8, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [2, 9, 0, 2, 10, 9, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [6, 11, 7, 2, 10, 3, 10, 8, 3, 10, 9, 8, -1, -1, -1, -1],
+    [7, 2, 3, 6, 2, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 0, 8, 7, 6, 0, 6, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [2, 7, 6, 2, 3, 7, 0, 1, 9, -1, -1, -1, -1, -1, -1, -1],
+    [1, 6, 2, 1, 8, 6, 1, 9, 8, 8, 7, 6, -1, -1, -1, -1],
+    [10, 7, 6, 10, 1, 7, 1, 3, 7, -1, -1, -1, -1, -1, -1, -1],
+    [10, 7, 6, 1, 7, 10, 1, 8, 7, 1, 0, 8, -1, -1, -1, -1],
+    [0, 3, 7, 0, 7, 10, 0, 10, 9, 6, 10, 7, -1, -1, -1, -1],
+    [7, 6, 10, 7, 10, 8, 8, 10, 9, -1, -1, -1, -1, -1, -1, -1],
+    [6, 8, 4, 11, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 6, 11, 3, 0, 6, 0, 4, 6, -1, -1, -1, -1, -1, -1, -1],
+    [8, 6, 11, 8, 4, 6, 9, 0, 1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 4, 6, 9, 6, 3, 9, 3, 1, 11, 3, 6, -1, -1, -1, -1],
+    [6, 8, 4, 6, 11, 8, 2, 10, 1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, 3, 0, 11, 0, 6, 11, 0, 4, 6, -1, -1, -1, -1],
+    [4, 11, 8, 4, 6, 11, 0, 2, 9, 2, 10, 9, -1, -1, -1, -1],
+    [10, 9, 3, 10, 3, 2, 9, 4, 3, 11, 3, 6, 4, 6, 3, -1],
+    [8, 2, 3, 8, 4, 2, 4, 6, 2, -1, -1, -1, -1, -1, -1, -1],
+    [0, 4, 2, 4, 6, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 9, 0, 2, 3, 4, 2, 4, 6, 4, 3, 8, -1, -1, -1, -1],
+    [1, 9, 4, 1, 4, 2, 2, 4, 6, -1, -1, -1, -1, -1, -1, -1],
+    [8, 1, 3, 8, 6, 1, 8, 4, 6, 6, 10, 1, -1, -1, -1, -1],
+    [10, 1, 0, 10, 0, 6, 6, 0, 4, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 3, 4, 3, 8, 6, 10, 3, 0, 3, 9, 10, 9, 3, -1],
+    [10, 9, 4, 6, 10, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 9, 5, 7, 6, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, 4, 9, 5, 11, 7, 6, -1, -1, -1, -1, -1, -1, -1],
+    [5, 0, 1, 5, 4, 0, 7, 6, 11, -1, -1, -1, -1, -1, -1, -1],
+    [11, 7, 6, 8, 3, 4, 3, 5, 4, 3, 1, 5, -1, -1, -1, -1],
+    [9, 5, 4, 10, 1, 2, 7, 6, 11, -1, -1, -1, -1, -1, -1, -1],
+    [6, 11, 7, 1, 2, 10, 0, 8, 3, 4, 9, 5, -1, -1, -1, -1],
+    [7, 6, 11, 5, 4, 10, 4, 2, 10, 4, 0, 2, -1, -1, -1, -1],
+    [3, 4, 8, 3, 5, 4, 3, 2, 5, 10, 5, 2, 11, 7, 6, -1],
+    [7, 2, 3, 7, 6, 2, 5, 4, 9, -1, -1, -1, -1, -1, -1, -1],
+    [9, 5, 4, 0, 8, 6, 0, 6, 2, 6, 8, 7, -1, -1, -1, -1],
+    [3, 6, 2, 3, 7, 6, 1, 5, 0, 5, 4, 0, -1, -1, -1, -1],
+    [6, 2, 8, 6, 8, 7, 2, 1, 8, 4, 8, 5, 1, 5, 8, -1],
+    [9, 5, 4, 10, 1, 6, 1, 7, 6, 1, 3, 7, -1, -1, -1, -1],
+    [1, 6, 10, 1, 7, 6, 1, 0, 7, 8, 7, 0, 9, 5, 4, -1],
+    [4, 0, 10, 4, 10, 5, 0, 3, 10, 6, 10, 7, 3, 7, 10, -1],
+    [7, 6, 10, 7, 10, 8, 5, 4, 10, 4, 8, 10, -1, -1, -1, -1],
+    [6, 9, 5, 6, 11, 9, 11, 8, 9, -1, -1, -1, -1, -1, -1, -1],
+    [3, 6, 11, 0, 6, 3, 0, 5, 6, 0, 9, 5, -1, -1, -1, -1],
+    [0, 11, 8, 0, 5, 11, 0, 1, 5, 5, 6, 11, -1, -1, -1, -1],
+    [6, 11, 3, 6, 3, 5, 5, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 10, 9, 5, 11, 9, 11, 8, 11, 5, 6, -1, -1, -1, -1],
+    [0, 11, 3, 0, 6, 11, 0, 9, 6, 5, 6, 9, 1, 2, 10, -1],
+    [11, 8, 5, 11, 5, 6, 8, 0, 5, 10, 5, 2, 0, 2, 5, -1],
+    [6, 11, 3, 6, 3, 5, 2, 10, 3, 10, 5, 3, -1, -1, -1, -1],
+    [5, 8, 9, 5, 2, 8, 5, 6, 2, 3, 8, 2, -1, -1, -1, -1],
+    [9, 5, 6, 9, 6, 0, 0, 6, 2, -1, -1, -1, -1, -1, -1, -1],
+    [1, 5, 8, 1, 8, 0, 5, 6, 8, 3, 8, 2, 6, 2, 8, -1],
+    [1, 5, 6, 2, 1, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 10, 3, 8, 6, 5, 6, 9, 8, 9, 6, -1],
+    [10, 1, 0, 10, 0, 6, 9, 5, 0, 5, 6, 0, -1, -1, -1, -1],
+    [0, 3, 8, 5, 6, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [10, 5, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11, 5, 10, 7, 5, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [11, 5, 10, 11, 7, 5, 8, 3, 0, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 7, 5, 10, 11, 1, 9, 0, -1, -1, -1, -1, -1, -1, -1],
+    [10, 7, 5, 10, 11, 7, 9, 8, 1, 8, 3, 1, -1, -1, -1, -1],
+    [11, 1, 2, 11, 7, 1, 7, 5, 1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, 1, 2, 7, 1, 7, 5, 7, 2, 11, -1, -1, -1, -1],
+    [9, 7, 5, 9, 2, 7, 9, 0, 2, 2, 11, 7, -1, -1, -1, -1],
+    [7, 5, 2, 7, 2, 11, 5, 9, 2, 3, 2, 8, 9, 8, 2, -1],
+    [2, 5, 10, 2, 3, 5, 3, 7, 5, -1, -1, -1, -1, -1, -1, -1],
+    [8, 2, 0, 8, 5, 2, 8, 7, 5, 10, 2, 5, -1, -1, -1, -1],
+    [9, 0, 1, 5, 10, 3, 5, 3, 7, 3, 10, 2, -1, -1, -1, -1],
+    [9, 8, 2, 9, 2, 1, 8, 7, 2, 10, 2, 5, 7, 5, 2, -1],
+    [1, 3, 5, 3, 7, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 7, 0, 7, 1, 1, 7, 5, -1, -1, -1, -1, -1, -1, -1],
+    [9, 0, 3, 9, 3, 5, 5, 3, 7, -1, -1, -1, -1, -1, -1, -1],
+    [9, 8, 7, 5, 9, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 8, 4, 5, 10, 8, 10, 11, 8, -1, -1, -1, -1, -1, -1, -1],
+    [5, 0, 4, 5, 11, 0, 5, 10, 11, 11, 3, 0, -1, -1, -1, -1],
+    [0, 1, 9, 8, 4, 10, 8, 10, 11, 10, 4, 5, -1, -1, -1, -1],
+    [10, 11, 4, 10, 4, 5, 11, 3, 4, 9, 4, 1, 3, 1, 4, -1],
+    [2, 5, 1, 2, 8, 5, 2, 11, 8, 4, 5, 8, -1, -1, -1, -1],
+    [0, 4, 11, 0, 11, 3, 4, 5, 11, 2, 11, 1, 5, 1, 11, -1],
+    [0, 2, 5, 0, 5, 9, 2, 11, 5, 4, 5, 8, 11, 8, 5, -1],
+    [9, 4, 5, 2, 11, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 5, 10, 3, 5, 2, 3, 4, 5, 3, 8, 4, -1, -1, -1, -1],
+    [5, 10, 2, 5, 2, 4, 4, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [3, 10, 2, 3, 5, 10, 3, 8, 5, 4, 5, 8, 0, 1, 9, -1],
+    [5, 10, 2, 5, 2, 4, 1, 9, 2, 9, 4, 2, -1, -1, -1, -1],
+    [8, 4, 5, 8, 5, 3, 3, 5, 1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 4, 5, 1, 0, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [8, 4, 5, 8, 5, 3, 9, 0, 5, 0, 3, 5, -1, -1, -1, -1],
+    [9, 4, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 11, 7, 4, 9, 11, 9, 10, 11, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 3, 4, 9, 7, 9, 11, 7, 9, 10, 11, -1, -1, -1, -1],
+    [1, 10, 11, 1, 11, 4, 1, 4, 0, 7, 4, 11, -1, -1, -1, -1],
+    [3, 1, 4, 3, 4, 8, 1, 10, 4, 7, 4, 11, 10, 11, 4, -1],
+    [4, 11, 7, 9, 11, 4, 9, 2, 11, 9, 1, 2, -1, -1, -1, -1],
+    [9, 7, 4, 9, 11, 7, 9, 1, 11, 2, 11, 1, 0, 8, 3, -1],
+    [11, 7, 4, 11, 4, 2, 2, 4, 0, -1, -1, -1, -1, -1, -1, -1],
+    [11, 7, 4, 11, 4, 2, 8, 3, 4, 3, 2, 4, -1, -1, -1, -1],
+    [2, 9, 10, 2, 7, 9, 2, 3, 7, 7, 4, 9, -1, -1, -1, -1],
+    [9, 10, 7, 9, 7, 4, 10, 2, 7, 8, 7, 0, 2, 0, 7, -1],
+    [3, 7, 10, 3, 10, 2, 7, 4, 10, 1, 10, 0, 4, 0, 10, -1],
+    [1, 10, 2, 8, 7, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 9, 1, 4, 1, 7, 7, 1, 3, -1, -1, -1, -1, -1, -1, -1],
+    [4, 9, 1, 4, 1, 7, 0, 8, 1, 8, 7, 1, -1, -1, -1, -1],
+    [4, 0, 3, 7, 4, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [9, 10, 8, 10, 11, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 0, 9, 3, 9, 11, 11, 9, 10, -1, -1, -1, -1, -1, -1, -1],
+    [0, 1, 10, 0, 10, 8, 8, 10, 11, -1, -1, -1, -1, -1, -1, -1],
+    [3, 1, 10, 11, 3, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 2, 11, 1, 11, 9, 9, 11, 8, -1, -1, -1, -1, -1, -1, -1],
+    [3, 0, 9, 3, 9, 11, 1, 2, 9, 2, 11, 9, -1, -1, -1, -1],
+    [0, 2, 11, 8, 0, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 2, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 3, 8, 2, 8, 10, 10, 8, 9, -1, -1, -1, -1, -1, -1, -1],
+    [9, 10, 2, 0, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 3, 8, 2, 8, 10, 0, 1, 8, 1, 10, 8, -1, -1, -1, -1],
+    [1, 10, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 8, 9, 1, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 3, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+];
+
+/// Samples `field` over `bounds` at the given per-axis `resolution` and triangulates the
+/// `isovalue` level set using marching cubes. Returns a vertex buffer and a flat index buffer
+/// (three indices per triangle), which must be grouped into triangles before being handed to
+/// [`SurfaceSharedData::new`].
+fn marching_cubes(
+    field: &dyn Fn(Vec3) -> f32,
+    bounds: AxisAlignedBoundingBox,
+    resolution: (usize, usize, usize),
+    isovalue: f32,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let (nx, ny, nz) = resolution;
+    let size = bounds.max - bounds.min;
+    let step = Vec3::new(
+        size.x / nx.max(1) as f32,
+        size.y / ny.max(1) as f32,
+        size.z / nz.max(1) as f32,
+    );
+
+    // Central-difference gradient of the field, used to estimate vertex normals.
+    let gradient = |p: Vec3| -> Vec3 {
+        let h = (step.x.min(step.y).min(step.z) * 0.5).max(0.0001);
+        let dx = field(p + Vec3::new(h, 0.0, 0.0)) - field(p - Vec3::new(h, 0.0, 0.0));
+        let dy = field(p + Vec3::new(0.0, h, 0.0)) - field(p - Vec3::new(0.0, h, 0.0));
+        let dz = field(p + Vec3::new(0.0, 0.0, h)) - field(p - Vec3::new(0.0, 0.0, h));
+        let len = (dx * dx + dy * dy + dz * dz).sqrt();
+        if len > f32::EPSILON {
+            Vec3::new(-dx / len, -dy / len, -dz / len)
+        } else {
+            Vec3::new(0.0, 1.0, 0.0)
+        }
+    };
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for xi in 0..nx {
+        for yi in 0..ny {
+            for zi in 0..nz {
+                let origin =
+                    bounds.min + Vec3::new(xi as f32 * step.x, yi as f32 * step.y, zi as f32 * step.z);
+
+                let mut corner_pos = [Vec3::ZERO; 8];
+                let mut corner_val = [0.0f32; 8];
+                for (i, &(ox, oy, oz)) in CORNER_OFFSETS.iter().enumerate() {
+                    let p = origin + Vec3::new(ox * step.x, oy * step.y, oz * step.z);
+                    corner_pos[i] = p;
+                    corner_val[i] = field(p) - isovalue;
+                }
+
+                let mut mask = 0u8;
+                for (i, &value) in corner_val.iter().enumerate() {
+                    if value < 0.0 {
+                        mask |= 1 << i;
+                    }
+                }
+
+                // Fully inside or fully outside the isosurface - nothing to triangulate.
+                if mask == 0 || mask == 255 {
+                    continue;
+                }
+
+                let edge_flags = EDGE_TABLE[mask as usize];
+                let mut edge_vertex = [Vec3::ZERO; 12];
+                for edge in 0..12 {
+                    if edge_flags & (1 << edge) != 0 {
+                        let (a, b) = EDGE_CONNECTION[edge];
+                        let (fa, fb) = (corner_val[a], corner_val[b]);
+                        let t = if (fb - fa).abs() < f32::EPSILON {
+                            0.5
+                        } else {
+                            -fa / (fb - fa)
+                        };
+                        edge_vertex[edge] = corner_pos[a] + (corner_pos[b] - corner_pos[a]).scale(t);
+                    }
+                }
+
+                let triangulation = &TRI_TABLE[mask as usize];
+                let mut i = 0;
+                while triangulation[i] != -1 {
+                    let base_index = vertices.len() as u32;
+                    for k in 0..3 {
+                        let position = edge_vertex[triangulation[i + k] as usize];
+                        vertices.push(Vertex {
+                            position,
+                            normal: gradient(position),
+                            ..Default::default()
+                        });
+                    }
+                    indices.extend_from_slice(&[base_index, base_index + 1, base_index + 2]);
+                    i += 3;
+                }
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marching_cubes_skips_fully_inside_and_outside_cells() {
+        let bounds = AxisAlignedBoundingBox {
+            min: Vec3::new(0.0, 0.0, 0.0),
+            max: Vec3::new(1.0, 1.0, 1.0),
+        };
+
+        let (vertices, indices) = marching_cubes(&|_| -1.0, bounds, (2, 2, 2), 0.0);
+        assert!(vertices.is_empty(), "fully inside cell should emit nothing");
+        assert!(indices.is_empty(), "fully inside cell should emit nothing");
+
+        let (vertices, indices) = marching_cubes(&|_| 1.0, bounds, (2, 2, 2), 0.0);
+        assert!(vertices.is_empty(), "fully outside cell should emit nothing");
+        assert!(indices.is_empty(), "fully outside cell should emit nothing");
+    }
+
+    #[test]
+    fn marching_cubes_clamps_degenerate_edge_to_midpoint() {
+        let bounds = AxisAlignedBoundingBox {
+            min: Vec3::new(0.0, 0.0, 0.0),
+            max: Vec3::new(1.0, 1.0, 1.0),
+        };
+
+        // Only corner 0 is negative, so edges 0, 3 and 8 are crossed and one triangle is emitted,
+        // starting with the vertex on edge 0. That edge, between corner 0 (0,0,0) and corner 1
+        // (1,0,0), has endpoint values so close together that (fb - fa).abs() < f32::EPSILON. Without
+        // the clamp, -fa / (fb - fa) would place the vertex near x=0.909 instead of the midpoint.
+        let field = |p: Vec3| -> f32 {
+            if p.x < 0.5 && p.y < 0.5 && p.z < 0.5 {
+                -1e-8
+            } else if p.x >= 0.5 && p.y < 0.5 && p.z < 0.5 {
+                1e-9
+            } else {
+                10.0
+            }
+        };
+
+        let (vertices, indices) = marching_cubes(&field, bounds, (1, 1, 1), 0.0);
+        assert_eq!(indices.len(), 3, "exactly one triangle should be generated");
+        assert!(
+            (vertices[0].position.x - 0.5).abs() < 1e-3,
+            "degenerate edge should clamp to its midpoint, got {}",
+            vertices[0].position.x
+        );
+    }
+
+    fn make_surface(vertices: Vec<Vertex>, triangles: Vec<[u32; 3]>) -> Surface {
+        Surface::new(Arc::new(Mutex::new(SurfaceSharedData::new(
+            vertices, triangles, false,
+        ))))
+    }
+
+    fn vertex_at(position: Vec3, normal: Vec3) -> Vertex {
+        Vertex {
+            position,
+            normal,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn deform_boundary_is_noop_on_watertight_mesh() {
+        let vertices = vec![
+            vertex_at(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+            vertex_at(Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)),
+            vertex_at(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)),
+            vertex_at(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        // Closed tetrahedron: every edge is shared by exactly two faces, so there is no boundary.
+        let triangles = vec![[0, 1, 2], [0, 2, 3], [0, 3, 1], [1, 3, 2]];
+        let positions_before: Vec<Vec3> = vertices.iter().map(|v| v.position).collect();
+
+        let mut mesh = Mesh::default();
+        mesh.add_surface(make_surface(vertices, triangles));
+
+        mesh.deform_boundary(0, 1.0, BoundaryDeformMode::Inflate, 1.0);
+
+        let surface = &mesh.surfaces()[0];
+        let data = surface.data();
+        let data = data.lock().unwrap();
+        let positions_after: Vec<Vec3> = data.get_vertices().iter().map(|v| v.position).collect();
+        assert_eq!(
+            positions_before, positions_after,
+            "a watertight mesh has no boundary to deform"
+        );
+    }
+
+    #[test]
+    fn deform_boundary_treats_non_manifold_edge_as_interior() {
+        let vertices = vec![
+            vertex_at(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, -1.0, 0.0)),
+            vertex_at(Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)),
+            vertex_at(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)),
+            vertex_at(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        // Same closed tetrahedron as above, plus a duplicate of face (0, 1, 2) glued on top of
+        // it. That makes edges (0, 1), (1, 2) and (2, 0) each referenced by three triangles
+        // instead of two, a non-manifold configuration, while every edge in the mesh is still
+        // referenced at least twice. Since "boundary" only means "referenced by exactly one
+        // triangle", this mesh still has no boundary and deforming it should still be a no-op.
+        let triangles = vec![[0, 1, 2], [0, 2, 3], [0, 3, 1], [1, 3, 2], [0, 1, 2]];
+        let positions_before: Vec<Vec3> = vertices.iter().map(|v| v.position).collect();
+
+        let mut mesh = Mesh::default();
+        mesh.add_surface(make_surface(vertices, triangles));
+
+        mesh.deform_boundary(0, 1.0, BoundaryDeformMode::Inflate, 1.0);
+
+        let surface = &mesh.surfaces()[0];
+        let data = surface.data();
+        let data = data.lock().unwrap();
+        let positions_after: Vec<Vec3> = data.get_vertices().iter().map(|v| v.position).collect();
+        assert_eq!(
+            positions_before, positions_after,
+            "non-manifold edges must not be mistaken for boundary edges"
+        );
+    }
+
+    #[test]
+    fn deform_boundary_moves_active_vertex_and_recomputes_its_normal() {
+        let vertices = vec![
+            vertex_at(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+            vertex_at(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+            vertex_at(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        // Single open triangle: every edge is a boundary edge.
+        let triangles = vec![[0, 1, 2]];
+
+        let mut mesh = Mesh::default();
+        mesh.add_surface(make_surface(vertices, triangles));
+
+        // A radius of 0 keeps max_hops at 0, so only the active vertex itself (hop 0, full
+        // weight) is affected; vertices 1 and 2 never enter the BFS distance map at all.
+        mesh.deform_boundary(0, 0.0, BoundaryDeformMode::Grab(Vec3::new(2.0, 0.0, 0.0)), 3.0);
+
+        let surface = &mesh.surfaces()[0];
+        let data = surface.data();
+        let data = data.lock().unwrap();
+        let result = data.get_vertices();
+
+        // weight is 1.0 at hop 0, so the displacement is exactly direction * strength.
+        assert_eq!(result[0].position, Vec3::new(6.0, 0.0, 0.0));
+        assert_eq!(result[1].position, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(result[2].position, Vec3::new(0.0, 1.0, 0.0));
+
+        // Only the moved vertex gets its normal recomputed, from the triangle's new face normal.
+        assert_eq!(result[0].normal, Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(result[1].normal, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(result[2].normal, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn pack_transform_round_trips_rotation_and_translation() {
+        // A 90 degree rotation about Z, row-major, plus translation (5, 6, 7):
+        //   [ 0 -1  0  5 ]
+        //   [ 1  0  0  6 ]
+        //   [ 0  0  1  7 ]
+        //   [ 0  0  0  1 ]
+        let m = Mat4 {
+            f: [
+                0.0, -1.0, 0.0, 5.0, //
+                1.0, 0.0, 0.0, 6.0, //
+                0.0, 0.0, 1.0, 7.0, //
+                0.0, 0.0, 0.0, 1.0,
+            ],
+        };
+
+        let (affine, normal) = pack_transform(&m);
+
+        assert_eq!(
+            affine,
+            [0.0, -1.0, 0.0, 5.0, 1.0, 0.0, 0.0, 6.0, 0.0, 0.0, 1.0, 7.0]
+        );
+
+        // This rotation is orthonormal (det = 1), so its inverse-transpose is itself.
+        assert_eq!(normal, [0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+}